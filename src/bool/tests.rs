@@ -0,0 +1,23 @@
+use crate::{assert_err, BoolLit, Literal};
+
+#[test]
+fn simple() {
+    assert_eq!(BoolLit::parse("true"), Ok(BoolLit::True));
+    assert_eq!(BoolLit::parse("false"), Ok(BoolLit::False));
+
+    assert!(BoolLit::True.value());
+    assert!(!BoolLit::False.value());
+    assert_eq!(BoolLit::True.as_str(), "true");
+    assert_eq!(BoolLit::False.as_str(), "false");
+
+    assert_eq!(Literal::parse("true"), Ok(Literal::Bool(BoolLit::True)));
+    assert_eq!(Literal::parse("false"), Ok(Literal::Bool(BoolLit::False)));
+}
+
+#[test]
+fn parse_err() {
+    assert_err!(BoolLit, "", InvalidLiteral, None);
+    assert_err!(BoolLit, "tru", InvalidLiteral, None);
+    assert_err!(BoolLit, "truee", InvalidLiteral, None);
+    assert_err!(BoolLit, "True", InvalidLiteral, None);
+}
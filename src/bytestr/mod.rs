@@ -1,6 +1,6 @@
 use std::{fmt, ops::Range};
 
-use crate::{Buffer, Error, ErrorKind::*, err::perr, escape::unescape};
+use crate::{Buffer, Error, ErrorKind::*, err::perr, escape::{self, Mode, unescape}, raw_string};
 
 
 /// A byte string or raw byte string literal, e.g. `b"hello"` or `br#"abc"def"#`.
@@ -36,6 +36,31 @@ impl<B: Buffer> ByteStringLit<B> {
         Self::parse_impl(input)
     }
 
+    /// Like [`Self::parse`], but keeps scanning after a malformed escape
+    /// instead of stopping at the first one, so a caller can collect every
+    /// escape problem in the literal in one pass (useful for e.g. an IDE
+    /// that wants to underline every bad escape at once instead of making
+    /// the user fix one and re-run).
+    ///
+    /// Returns every escape error encountered, in source order, and `Some`
+    /// literal only if there were none: a `ByteStringLit` that callers can
+    /// inspect further (e.g. via [`Self::value`] or
+    /// [`Self::raw_range_of_value_index`]) must have a fully decoded value,
+    /// so a literal with one or more bad escapes yields `None` alongside
+    /// its errors. An error that concerns the literal as a whole (e.g. a
+    /// missing closing quote) is returned alone. A raw byte string literal
+    /// never has escapes, so its `Vec` holds at most one error.
+    pub fn parse_collecting(input: B) -> (Option<Self>, Vec<Error>) {
+        if input.is_empty() {
+            return (None, vec![perr(None, Empty)]);
+        }
+        if !input.starts_with(r#"b""#) && !input.starts_with("br") {
+            return (None, vec![perr(None, InvalidByteStringLiteralStart)]);
+        }
+
+        Self::parse_collecting_impl(input)
+    }
+
     /// Returns the string value this literal represents (where all escapes have
     /// been turned into their respective values).
     pub fn value(&self) -> &[u8] {
@@ -58,6 +83,71 @@ impl<B: Buffer> ByteStringLit<B> {
         self.num_hashes.is_some()
     }
 
+    /// Returns the byte range within `self.raw` (i.e. the original,
+    /// possibly-escaped source) that produced the byte at `value_idx` within
+    /// `self.value()`.
+    ///
+    /// Every escape (`\xHH`, `\n`, ...) produces exactly one byte of value,
+    /// so unlike [`crate::StringLit::raw_range_of_value_index`] there is no need to
+    /// special-case multi-byte units here.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `value_idx` is not a valid index into `self.value()`.
+    pub fn raw_range_of_value_index(&self, value_idx: usize) -> Range<usize> {
+        assert!(value_idx < self.value().len(), "value index out of bounds");
+
+        let inner = self.inner_range();
+        if self.value.is_none() {
+            // No escapes: `value` is a verbatim copy of `raw[inner]`.
+            return inner.start + value_idx..inner.start + value_idx + 1;
+        }
+
+        let mut value_pos = 0;
+        let mut i = inner.start;
+        while i < inner.end {
+            if self.raw.as_bytes()[i] == b'\\' {
+                let (_, len) = unescape(&self.raw[i..inner.end], i, Mode::ByteStr)
+                    .expect("already validated while parsing");
+                if value_idx == value_pos {
+                    return i..i + len;
+                }
+                value_pos += 1;
+                i += len;
+            } else {
+                if value_idx == value_pos {
+                    return i..i + 1;
+                }
+                value_pos += 1;
+                i += 1;
+            }
+        }
+        unreachable!("value_idx was already checked to be in bounds")
+    }
+
+    /// Iterates over the unescaped value of this literal without allocating,
+    /// invoking `cb` once per byte with the range in `self.raw` (i.e. the
+    /// original, possibly escaped, source) that produced it, together with
+    /// either the byte or the error that occurred while decoding it.
+    ///
+    /// Scanning continues after an error instead of stopping at the first
+    /// one, so a single call can be used to collect every problem in the
+    /// literal at once.
+    pub fn unescape_with(&self, mut cb: impl FnMut(Range<usize>, Result<u8, Error>)) {
+        let inner = self.inner_range();
+        if self.is_raw_byte_string() {
+            for (i, &b) in self.raw.as_bytes()[inner.clone()].iter().enumerate() {
+                let pos = inner.start + i;
+                cb(pos..pos + 1, Ok(b));
+            }
+        } else {
+            escape::unescape_with(
+                &self.raw[inner.clone()], inner.start, Mode::ByteStr,
+                |range, res| cb(range, res.map(|u| u.as_byte())),
+            );
+        }
+    }
+
     /// The range within `self.raw` that excludes the quotes and potential `r#`.
     fn inner_range(&self) -> Range<usize> {
         match self.num_hashes {
@@ -68,83 +158,86 @@ impl<B: Buffer> ByteStringLit<B> {
 
     /// Precondition: input has to start with either `b"` or `br`.
     pub(crate) fn parse_impl(input: B) -> Result<Self, Error> {
+        match Self::parse_collecting_impl(input) {
+            (Some(lit), errors) if errors.is_empty() => Ok(lit),
+            (_, mut errors) => Err(errors.remove(0)),
+        }
+    }
+
+    /// Precondition: input has to start with either `b"` or `br`. See
+    /// [`Self::parse_collecting`] for the semantics.
+    pub(crate) fn parse_collecting_impl(input: B) -> (Option<Self>, Vec<Error>) {
         if input.starts_with(r"br") {
-            // Raw string literal
-            let num_hashes = input[2..].bytes().position(|b| b != b'#')
-                .ok_or(perr(None, InvalidLiteral))?;
+            return match Self::parse_raw_impl(input) {
+                Ok(lit) => (Some(lit), Vec::new()),
+                Err(e) => (None, vec![e]),
+            };
+        }
 
-            if input.as_bytes().get(2 + num_hashes) != Some(&b'"') {
-                return Err(perr(None, InvalidLiteral));
+        // First, find the closing quote without decoding any escapes: every
+        // escape's textual form (digits, `x`, braces) consists of bytes that
+        // never collide with a literal `"` or `\r`, so skipping the `\`
+        // together with whatever byte follows it is enough to avoid
+        // mistaking an escaped `\"` or `\\` for the end of the literal.
+        let mut i = 2;
+        while i < input.len() - 1 {
+            match input.as_bytes()[i] {
+                b'\\' => i += 2.min(input.len() - i),
+                b'\r' if input.as_bytes()[i + 1] != b'\n'
+                    => return (None, vec![perr(i, IsolatedCr)]),
+                b'"' => return (None, vec![perr(i + 1..input.len(), UnexpectedChar)]),
+                _ => i += 1,
             }
-            let start_inner = 2 + num_hashes + 1;
-            let hashes = &input[2..num_hashes + 2];
-
-            let mut closing_quote_pos = None;
-            for (i, b) in input[start_inner..].bytes().enumerate() {
-                if b == b'"' && input[start_inner + i + 1..].starts_with(hashes) {
-                    closing_quote_pos = Some(i + start_inner);
-                    break;
-                }
+        }
 
-                if !b.is_ascii() {
-                    return Err(perr(i + start_inner, NonAsciiInByteLiteral));
+        // `i` only overshoots to `input.len()` if the last `"` was escaped.
+        if i == input.len() || input.as_bytes()[input.len() - 1] != b'"' || input.len() == 2 {
+            return (None, vec![perr(None, UnterminatedString)]);
+        }
+
+        // Now that the literal's extent is known, decode its content with
+        // the shared escape engine, which already knows how to collect
+        // every error instead of stopping at the first one.
+        let mut had_escape = false;
+        let mut value = Vec::new();
+        let mut errors = Vec::new();
+        escape::unescape_with(&input[2..input.len() - 1], 2, Mode::ByteStr, |range, res| {
+            match res {
+                Ok(unit) => {
+                    had_escape |= input.as_bytes()[range.start] == b'\\';
+                    value.push(unit.as_byte());
                 }
+                Err(e) => errors.push(e),
             }
-            let closing_quote_pos = closing_quote_pos
-                .ok_or(perr(None, UnterminatedRawString))?;
+        });
 
-            if closing_quote_pos + num_hashes != input.len() - 1 {
-                return Err(perr(closing_quote_pos + num_hashes + 1..input.len(), UnexpectedChar));
-            }
+        if !errors.is_empty() {
+            return (None, errors);
+        }
 
-            Ok(Self {
-                raw: input,
-                value: None,
-                num_hashes: Some(num_hashes as u32),
-            })
-        } else {
-            let mut i = 2;
-            let mut end_last_escape = 2;
-            let mut value = Vec::new();
-            while i < input.len() - 1 {
-                match input.as_bytes()[i] {
-                    b'\\' => {
-                        let (b, len) = unescape::<u8>(&input[i..input.len() - 1], i)?;
-                        value.extend_from_slice(&input.as_bytes()[end_last_escape..i]);
-                        value.push(b);
-                        i += len;
-                        end_last_escape = i;
-                    }
-                    b'\r' if input.as_bytes()[i + 1] != b'\n'
-                        => return Err(perr(i, IsolatedCr)),
-                    b'"' => return Err(perr(i + 1..input.len(), UnexpectedChar)),
-                    b if !b.is_ascii()
-                        => return Err(perr(i, NonAsciiInByteLiteral)),
-                    _ => i += 1,
-                }
-            }
+        // `value` is only `None` if there was no escape in the input string
+        // (with the special case of the input being empty). This means the
+        // string value basically equals the input, so we store `None`.
+        let value = if had_escape { Some(value) } else { None };
+        (Some(Self { raw: input, value, num_hashes: None }), Vec::new())
+    }
 
-            if input.as_bytes()[input.len() - 1] != b'"' || input.len() == 2 {
-                return Err(perr(None, UnterminatedString));
+    /// Precondition: input has to start with `br`. Raw byte string literals
+    /// never contain escapes, so there's never more than one error to
+    /// report.
+    fn parse_raw_impl(input: B) -> Result<Self, Error> {
+        let num_hashes = raw_string::scan_raw_body(&input, 2, |i, b| {
+            if !b.is_ascii() {
+                return Err(perr(i, NonAsciiInByteLiteral));
             }
+            Ok(())
+        })?;
 
-            // `value` is only empty there was no escape in the input string
-            // (with the special case of the input being empty). This means the
-            // string value basically equals the input, so we store `None`.
-            let value = if value.is_empty() {
-                None
-            } else {
-                // There was an escape in the string, so we need to push the
-                // remaining unescaped part of the string still.
-                value.extend_from_slice(&input.as_bytes()[end_last_escape..input.len() - 1]);
-                Some(value)
-            };
-            Ok(Self {
-                raw: input,
-                value,
-                num_hashes: None,
-            })
-        }
+        Ok(Self {
+            raw: input,
+            value: None,
+            num_hashes: Some(num_hashes),
+        })
     }
 }
 
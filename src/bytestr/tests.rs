@@ -0,0 +1,149 @@
+use crate::{assert_err, ByteStringLit, ErrorKind, Literal, test_util::assert_parse_ok_eq};
+
+// ===== Utility functions =======================================================================
+
+macro_rules! check {
+    ($lit:literal, $has_escapes:expr, $num_hashes:expr) => {
+        let input = stringify!($lit);
+        let expected = ByteStringLit {
+            raw: input,
+            value: if $has_escapes { Some($lit.to_vec()) } else { None },
+            num_hashes: $num_hashes,
+        };
+
+        assert_parse_ok_eq(
+            input, ByteStringLit::parse(input), expected.clone(), "ByteStringLit::parse");
+        assert_parse_ok_eq(
+            input, Literal::parse(input), Literal::ByteString(expected), "Literal::parse");
+        assert_eq!(ByteStringLit::parse(input).unwrap().value(), $lit.as_slice());
+        assert_eq!(&*ByteStringLit::parse(input).unwrap().into_value(), $lit.as_slice());
+    };
+}
+
+
+// ===== Actual tests ============================================================================
+
+#[test]
+fn simple() {
+    check!(b"", false, None);
+    check!(b"a", false, None);
+    check!(b"peter", false, None);
+    check!(b"lit af", false, None);
+}
+
+#[test]
+fn simple_escapes() {
+    check!(b"a\nb", true, None);
+    check!(b"\t cat \r dog\n rabbit \0mouse", true, None);
+    check!(b"\x00", true, None);
+    check!(b"\x7f", true, None);
+    check!(b"\x80", true, None);
+    check!(b"\xff", true, None);
+}
+
+#[test]
+fn raw_byte_string() {
+    check!(br"", false, Some(0));
+    check!(br"peter", false, Some(0));
+    check!(br#"foo " bar"#, false, Some(1));
+    check!(br##"foo " bar"##, false, Some(2));
+}
+
+#[test]
+fn parse_err() {
+    assert_err!(ByteStringLit, r#"b""#, UnterminatedString, None);
+    assert_err!(ByteStringLit, r#"b"foo"#, UnterminatedString, None);
+    assert_err!(ByteStringLit, "b\"fo\rx\"", IsolatedCr, 4);
+}
+
+#[test]
+fn non_ascii_is_rejected() {
+    // Non-raw byte strings go through the shared escape engine, which
+    // reports the whole invalid character's span rather than just its first
+    // byte; raw byte strings are scanned byte-by-byte and so report only
+    // the lead byte.
+    assert_err!(ByteStringLit, "b\"fo\u{1f98a}x\"", NonAsciiInByteLiteral, 4..8);
+    assert_err!(ByteStringLit, "br\"fo\u{1f98a}x\"", NonAsciiInByteLiteral, 5);
+}
+
+#[test]
+fn raw_range_of_value_index_no_escapes() {
+    let lit = ByteStringLit::parse("b\"abc\"").unwrap();
+    assert_eq!(lit.value(), b"abc");
+    for i in 0..lit.value().len() {
+        assert_eq!(lit.raw_range_of_value_index(i), 2 + i..2 + i + 1);
+    }
+}
+
+#[test]
+fn raw_range_of_value_index_with_escapes() {
+    let lit = ByteStringLit::parse("b\"a\\nb\\xffc\"").unwrap();
+    assert_eq!(lit.value(), b"a\nb\xffc");
+    assert_eq!(lit.raw_range_of_value_index(0), 2..3); // 'a'
+    assert_eq!(lit.raw_range_of_value_index(1), 3..5); // '\n'
+    assert_eq!(lit.raw_range_of_value_index(2), 5..6); // 'b'
+    assert_eq!(lit.raw_range_of_value_index(3), 6..10); // '\xff'
+    assert_eq!(lit.raw_range_of_value_index(4), 10..11); // 'c'
+}
+
+#[test]
+fn parse_collecting_multiple_errors() {
+    let (lit, errors) = ByteStringLit::parse_collecting("b\"\\a\\y\"");
+    assert_eq!(lit, None);
+    let kinds_and_spans: Vec<_> = errors.iter().map(|e| (e.kind(), e.span())).collect();
+    assert_eq!(kinds_and_spans, vec![
+        (ErrorKind::UnknownEscape, Some(2..4)),
+        (ErrorKind::UnknownEscape, Some(4..6)),
+    ]);
+}
+
+#[test]
+fn parse_collecting_unterminated_string_is_single_error() {
+    let (lit, errors) = ByteStringLit::parse_collecting(r#"b"foo"#);
+    assert_eq!(lit, None);
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].kind(), ErrorKind::UnterminatedString);
+    assert_eq!(errors[0].span(), None);
+}
+
+#[test]
+fn parse_collecting_ok_has_no_errors() {
+    let (lit, errors) = ByteStringLit::parse_collecting("b\"a\\nb\"");
+    assert!(errors.is_empty());
+    assert_eq!(lit.unwrap().value(), b"a\nb");
+}
+
+#[test]
+fn parse_agrees_with_parse_collecting_first_error() {
+    let (_, mut errors) = ByteStringLit::parse_collecting("b\"\\a\\y\"");
+    let first = errors.remove(0);
+    let err = ByteStringLit::parse("b\"\\a\\y\"").unwrap_err();
+    assert_eq!(err, first);
+}
+
+#[test]
+fn unescape_with_values_and_ranges() {
+    let lit = ByteStringLit::parse("b\"a\\nb\\xffc\"").unwrap();
+    let mut out = Vec::new();
+    lit.unescape_with(|range, res| out.push((range, res.unwrap())));
+    assert_eq!(out, vec![
+        (2..3, b'a'),
+        (3..5, b'\n'),
+        (5..6, b'b'),
+        (6..10, 0xffu8),
+        (10..11, b'c'),
+    ]);
+}
+
+#[test]
+fn unescape_with_raw_byte_string() {
+    let lit = ByteStringLit::parse("br\"a\\nb\"").unwrap();
+    let mut out = Vec::new();
+    lit.unescape_with(|range, res| out.push((range, res.unwrap())));
+    assert_eq!(out, vec![
+        (3..4, b'a'),
+        (4..5, b'\\'),
+        (5..6, b'n'),
+        (6..7, b'b'),
+    ]);
+}
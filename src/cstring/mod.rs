@@ -0,0 +1,156 @@
+use std::{fmt, ops::Range};
+
+use crate::{Buffer, Error, ErrorKind::*, err::perr, escape::{unescape, Mode}, raw_string};
+
+
+/// A C-string or raw C-string literal, e.g. `c"hello"` or `cr#"abc"def"#`.
+///
+/// See [the reference][ref] for more information.
+///
+/// [ref]: https://doc.rust-lang.org/reference/tokens.html#c-string-and-raw-c-string-literals
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CStringLit<B: Buffer> {
+    /// The raw input.
+    raw: B,
+
+    /// The string value (with all escapes unescaped, and *without* the
+    /// implicit trailing NUL that a C-string has at runtime), or `None` if
+    /// there were no escapes. In the latter case, `inner_range` of `raw` is
+    /// the string value.
+    value: Option<Vec<u8>>,
+
+    /// The number of hash signs in case of a raw C-string literal, or `None`
+    /// if it's not a raw C-string literal.
+    num_hashes: Option<u32>,
+}
+
+impl<B: Buffer> CStringLit<B> {
+    /// Parses the input as a (raw) C-string literal. Returns an error if the
+    /// input is invalid or represents a different kind of literal.
+    pub fn parse(input: B) -> Result<Self, Error> {
+        if input.is_empty() {
+            return Err(perr(None, Empty));
+        }
+        if !input.starts_with("c\"") && !input.starts_with("cr") {
+            return Err(perr(None, InvalidCStringLiteralStart));
+        }
+
+        Self::parse_impl(input)
+    }
+
+    /// Returns the byte value this literal represents (where all escapes
+    /// have been turned into their respective values), *without* the
+    /// implicit trailing NUL that the literal has at runtime.
+    pub fn value(&self) -> &[u8] {
+        self.value.as_deref().unwrap_or(&self.raw.as_bytes()[self.inner_range()])
+    }
+
+    /// Like `value` but returns a potentially owned version of the value.
+    ///
+    /// The return value is either `Cow<'static, [u8]>` if `B = String`, or
+    /// `Cow<'a, [u8]>` if `B = &'a str`.
+    pub fn into_value(self) -> B::ByteCow {
+        let inner_range = self.inner_range();
+        let Self { raw, value, .. } = self;
+        value.map(B::ByteCow::from).unwrap_or_else(|| raw.cut(inner_range).into_byte_cow())
+    }
+
+    /// Returns whether this literal is a raw C-string literal (starting with
+    /// `cr`).
+    pub fn is_raw(&self) -> bool {
+        self.num_hashes.is_some()
+    }
+
+    /// The range within `self.raw` that excludes the `c`/`cr#` prefix, the
+    /// quotes and potential trailing hashes.
+    fn inner_range(&self) -> Range<usize> {
+        match self.num_hashes {
+            None => 2..self.raw.len() - 1,
+            Some(n) => 2 + n as usize + 1..self.raw.len() - n as usize - 1,
+        }
+    }
+
+    /// Precondition: input has to start with either `c"` or `cr`.
+    pub(crate) fn parse_impl(input: B) -> Result<Self, Error> {
+        if input.starts_with("cr") {
+            // Raw C-string literal
+            let num_hashes = raw_string::scan_raw_body(&input, 2, |i, b| {
+                if b == 0 {
+                    return Err(perr(i, NulInCStr));
+                }
+                if b == b'\r' && input.as_bytes().get(i + 1) != Some(&b'\n') {
+                    return Err(perr(i, IsolatedCr));
+                }
+                Ok(())
+            })?;
+
+            Ok(Self {
+                raw: input,
+                value: None,
+                num_hashes: Some(num_hashes),
+            })
+        } else {
+            let mut i = 2;
+            let mut end_last_escape = 2;
+            let mut value = Vec::new();
+            while i < input.len() - 1 {
+                match input.as_bytes()[i] {
+                    b'\\' => {
+                        let (unit, len) = unescape(&input[i..input.len() - 1], i, Mode::CStr)?;
+                        value.extend_from_slice(input[end_last_escape..i].as_bytes());
+                        unit.push_to(&mut value);
+                        i += len;
+                        end_last_escape = i;
+                    }
+                    0 => return Err(perr(i, NulInCStr)),
+                    b'\r' if input.as_bytes()[i + 1] != b'\n'
+                        => return Err(perr(i, IsolatedCr)),
+                    b'"' => return Err(perr(i + 1..input.len(), UnexpectedChar)),
+                    _ => i += input[i..].chars().next().map_or(1, char::len_utf8),
+                }
+            }
+
+            if input.as_bytes()[input.len() - 1] != b'"' || input.len() == 2 {
+                return Err(perr(None, UnterminatedString));
+            }
+
+            // `value` is only empty if there was no escape in the input
+            // string (with the special case of the input being empty). This
+            // means the string value basically equals the input, so we
+            // store `None`.
+            let value = if value.is_empty() {
+                None
+            } else {
+                value.extend_from_slice(input[end_last_escape..input.len() - 1].as_bytes());
+                Some(value)
+            };
+            Ok(Self {
+                raw: input,
+                value,
+                num_hashes: None,
+            })
+        }
+    }
+}
+
+impl CStringLit<&str> {
+    /// Makes a copy of the underlying buffer and returns the owned version
+    /// of `Self`.
+    pub fn into_owned(self) -> CStringLit<String> {
+        CStringLit {
+            raw: self.raw.to_owned(),
+            value: self.value,
+            num_hashes: self.num_hashes,
+        }
+    }
+}
+
+impl<B: Buffer> fmt::Display for CStringLit<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(&self.raw)
+    }
+}
+
+
+#[cfg(test)]
+mod tests;
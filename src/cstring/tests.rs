@@ -0,0 +1,112 @@
+use crate::{assert_err, Literal, CStringLit, test_util::assert_parse_ok_eq};
+
+// ===== Utility functions =======================================================================
+
+macro_rules! check {
+    ($lit:literal, $has_escapes:expr, $num_hashes:expr) => {
+        let input = stringify!($lit);
+        let expected = CStringLit {
+            raw: input,
+            value: if $has_escapes { Some($lit.to_bytes().to_vec()) } else { None },
+            num_hashes: $num_hashes,
+        };
+
+        assert_parse_ok_eq(input, CStringLit::parse(input), expected.clone(), "CStringLit::parse");
+        assert_parse_ok_eq(
+            input, Literal::parse(input), Literal::CString(expected), "Literal::parse");
+        assert_eq!(CStringLit::parse(input).unwrap().value(), $lit.to_bytes());
+        assert_eq!(&*CStringLit::parse(input).unwrap().into_value(), $lit.to_bytes());
+    };
+}
+
+
+// ===== Actual tests ============================================================================
+
+#[test]
+fn simple() {
+    check!(c"", false, None);
+    check!(c"a", false, None);
+    check!(c"peter", false, None);
+    check!(c"Sei gegrüßt, Bärthelt!", false, None);
+    check!(c"lit 👌 😂 af", false, None);
+}
+
+#[test]
+fn simple_escapes() {
+    check!(c"a\nb", true, None);
+    check!(c"\t猫\r馬\n うさぎ", true, None);
+    check!(c"\x01\x7f", true, None);
+    check!(c"\x80\xff", true, None);
+}
+
+#[test]
+fn unicode_escapes() {
+    check!(c"\u{7e}", true, None);
+    check!(c"నక్క\u{E4}", true, None);
+    check!(c"\u{2764}Füchsin", true, None);
+}
+
+#[test]
+fn raw_c_string() {
+    check!(cr"", false, Some(0));
+    check!(cr"peter", false, Some(0));
+    check!(cr#"foo " bar"#, false, Some(1));
+    check!(cr##"foo " bar"##, false, Some(2));
+}
+
+#[test]
+fn parse_err() {
+    assert_err!(CStringLit, r#"c""#, UnterminatedString, None);
+    assert_err!(CStringLit, r#"c"foo"#, UnterminatedString, None);
+    assert_err!(CStringLit, r#"c"fox"peter"#, UnexpectedChar, 6..11);
+    assert_err!(CStringLit, "c\"fo\rx\"", IsolatedCr, 4);
+    assert_err!(CStringLit, "cr\"fo\rx\"", IsolatedCr, 5);
+}
+
+#[test]
+fn invald_escapes() {
+    assert_err!(CStringLit, r#"c"\a""#, UnknownEscape, 2..4);
+    assert_err!(CStringLit, r#"c"foo\y""#, UnknownEscape, 5..7);
+    assert_err!(CStringLit, r#"c"\"#, UnterminatedString, None);
+    assert_err!(CStringLit, r#"c"\x""#, UnterminatedEscape, 2..4);
+    assert_err!(CStringLit, r#"c"🦊\x1""#, UnterminatedEscape, 6..9);
+    assert_err!(CStringLit, r#"c" \xaj""#, InvalidXEscape, 3..7);
+    assert_err!(CStringLit, r#"c"నక్క\xjb""#, InvalidXEscape, 14..18);
+}
+
+#[test]
+fn invalid_unicode_escapes() {
+    assert_err!(CStringLit, r#"c"\u""#, UnicodeEscapeWithoutBrace, 2..4);
+    assert_err!(CStringLit, r#"c"🦊\u ""#, UnicodeEscapeWithoutBrace, 6..8);
+    assert_err!(CStringLit, r#"c"\u3""#, UnicodeEscapeWithoutBrace, 2..4);
+
+    assert_err!(CStringLit, r#"c"\u{""#, UnterminatedUnicodeEscape, 2..5);
+    assert_err!(CStringLit, r#"c"\u{12""#, UnterminatedUnicodeEscape, 2..7);
+    assert_err!(CStringLit, r#"c"🦊\u{a0b""#, UnterminatedUnicodeEscape, 6..12);
+    assert_err!(CStringLit, r#"c"\u{a0_b  ""#, UnterminatedUnicodeEscape, 2..11);
+
+    assert_err!(CStringLit, r#"c"\u{_}నక్క""#, InvalidStartOfUnicodeEscape, 5);
+    assert_err!(CStringLit, r#"c"\u{_5f}""#, InvalidStartOfUnicodeEscape, 5);
+
+    assert_err!(CStringLit, r#"c"fox\u{x}""#, NonHexDigitInUnicodeEscape, 8);
+    assert_err!(CStringLit, r#"c"\u{0x}🦊""#, NonHexDigitInUnicodeEscape, 6);
+    assert_err!(CStringLit, r#"c"నక్క\u{3bx}""#, NonHexDigitInUnicodeEscape, 19);
+    assert_err!(CStringLit, r#"c"\u{3b_x}лиса""#, NonHexDigitInUnicodeEscape, 8);
+    assert_err!(CStringLit, r#"c"\u{4x_}""#, NonHexDigitInUnicodeEscape, 6);
+
+    assert_err!(CStringLit, r#"c"\u{1234567}""#, TooManyDigitInUnicodeEscape, 11);
+    assert_err!(CStringLit, r#"c"నక్క\u{1234567}🦊""#, TooManyDigitInUnicodeEscape, 23);
+    assert_err!(CStringLit, r#"c"నక్క\u{1_23_4_56_7}""#, TooManyDigitInUnicodeEscape, 27);
+    assert_err!(CStringLit, r#"c"\u{abcdef123}лиса""#, TooManyDigitInUnicodeEscape, 11);
+
+    assert_err!(CStringLit, r#"c"\u{110000}fox""#, InvalidUnicodeEscapeChar, 2..11);
+}
+
+#[test]
+fn nul_is_rejected() {
+    assert_err!(CStringLit, "c\"fo\0x\"", NulInCStr, 4);
+    assert_err!(CStringLit, r#"c"\0""#, NulInCStr, 2..4);
+    assert_err!(CStringLit, r#"c"\x00""#, NulInCStr, 2..6);
+    assert_err!(CStringLit, r#"c"\u{0}""#, NulInCStr, 2..7);
+    assert_err!(CStringLit, "cr\"fo\0x\"", NulInCStr, 5);
+}
@@ -0,0 +1,180 @@
+use std::{fmt, ops::Range};
+
+
+/// An error that occurred while parsing or inspecting a literal.
+///
+/// This type does not expose its message as a string to compare against:
+/// use [`Error::kind`] to match on the
+/// specific problem and [`Error::span`] to get the byte range (relative to
+/// the input that was parsed) that caused it, if one is available.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Error {
+    span: Option<Range<usize>>,
+    kind: ErrorKind,
+}
+
+impl Error {
+    /// The byte range within the parsed input that is responsible for this
+    /// error, or `None` if the error is not attributable to a specific
+    /// region (e.g. the input being completely empty).
+    pub fn span(&self) -> Option<Range<usize>> {
+        self.span.clone()
+    }
+
+    /// The kind of error that occurred.
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.kind, f)
+    }
+}
+
+impl std::error::Error for Error {}
+
+
+/// The different kinds of errors that can occur while parsing a literal.
+///
+/// This type is intentionally `#[non_exhaustive]`: new literal kinds and new
+/// escape rules may need to report new kinds of errors in the future.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// The input was empty.
+    Empty,
+
+    /// The input does not start with a valid prefix/quote for the literal
+    /// kind that was requested.
+    InvalidLiteral,
+
+    /// A byte string literal did not start with `b"` or `br`.
+    InvalidByteStringLiteralStart,
+
+    /// A C-string literal did not start with `c"` or `cr`.
+    InvalidCStringLiteralStart,
+
+    /// There was a character after the closing quote that shouldn't be
+    /// there.
+    UnexpectedChar,
+
+    /// The string literal was not terminated (no closing quote found).
+    UnterminatedString,
+
+    /// The raw string/byte-string/C-string literal was not terminated (no
+    /// matching closing quote + hashes found).
+    UnterminatedRawString,
+
+    /// A `\` was not followed by enough characters to form a complete escape
+    /// (e.g. nothing at all, or fewer than two characters after `\x`).
+    UnterminatedEscape,
+
+    /// A `\u{...}` escape was not terminated with a `}`.
+    UnterminatedUnicodeEscape,
+
+    /// A lone `\r` was found that is not immediately followed by `\n`.
+    IsolatedCr,
+
+    /// An escape sequence `\X` was found where `X` is not a recognized
+    /// escape character.
+    UnknownEscape,
+
+    /// A `\x` escape was not followed by two hex digits.
+    InvalidXEscape,
+
+    /// A `\xHH` escape produced a value above `0x7F` in a context where
+    /// that's not allowed (`StringLit`).
+    NonAsciiXEscape,
+
+    /// A `\u` was not immediately followed by `{`.
+    UnicodeEscapeWithoutBrace,
+
+    /// The first character after `\u{` was `_`, which is not allowed.
+    InvalidStartOfUnicodeEscape,
+
+    /// A character inside `\u{...}` was neither a hex digit nor `_`.
+    NonHexDigitInUnicodeEscape,
+
+    /// More than six hex digits were found inside `\u{...}`.
+    TooManyDigitInUnicodeEscape,
+
+    /// The hex digits inside `\u{...}` do not form a valid Unicode scalar
+    /// value.
+    InvalidUnicodeEscapeChar,
+
+    /// A byte string literal (or its raw variant) contained a non-ASCII
+    /// character in the source, where only ASCII is allowed.
+    NonAsciiInByteLiteral,
+
+    /// A C-string literal contained a NUL byte, either directly in the
+    /// source or produced by an escape (`\0`, `\x00` or `\u{0}`). C-strings
+    /// may never contain an interior NUL.
+    NulInCStr,
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::Empty => "input is empty",
+            Self::InvalidLiteral => "invalid literal",
+            Self::InvalidByteStringLiteralStart
+                => "byte string literal must start with `b\"` or `br`",
+            Self::InvalidCStringLiteralStart
+                => "C-string literal must start with `c\"` or `cr`",
+            Self::UnexpectedChar => "unexpected character after literal",
+            Self::UnterminatedString => "unterminated string literal",
+            Self::UnterminatedRawString => "unterminated raw string literal",
+            Self::UnterminatedEscape => "unterminated escape sequence",
+            Self::UnterminatedUnicodeEscape => "unterminated unicode escape sequence",
+            Self::IsolatedCr => "isolated carriage return (`\\r`) not followed by `\\n`",
+            Self::UnknownEscape => "unknown escape sequence",
+            Self::InvalidXEscape => "invalid character in `\\x` escape",
+            Self::NonAsciiXEscape => "`\\x` escape in this context must be in range [0x00, 0x7F]",
+            Self::UnicodeEscapeWithoutBrace => "`\\u` must be followed by `{`",
+            Self::InvalidStartOfUnicodeEscape => "unicode escape must not start with `_`",
+            Self::NonHexDigitInUnicodeEscape => "invalid character in unicode escape",
+            Self::TooManyDigitInUnicodeEscape => "overlong unicode escape",
+            Self::InvalidUnicodeEscapeChar => "unicode escape does not form a valid character",
+            Self::NonAsciiInByteLiteral => "non-ASCII character in byte string literal",
+            Self::NulInCStr => "C-string literal must not contain a NUL byte",
+        };
+        f.write_str(s)
+    }
+}
+
+
+/// Converts a value describing "where in the input did this go wrong" into
+/// the `Option<Range<usize>>` stored inside an [`Error`].
+///
+/// This exists so that call sites can pass `None` (no location), a `usize`
+/// (a single byte) or a `Range<usize>` (a span) directly to [`perr`] without
+/// having to wrap everything in `Some(..)` by hand.
+pub(crate) trait IntoSpan {
+    fn into_span(self) -> Option<Range<usize>>;
+}
+
+impl IntoSpan for Option<Range<usize>> {
+    fn into_span(self) -> Option<Range<usize>> {
+        self
+    }
+}
+
+impl IntoSpan for Range<usize> {
+    fn into_span(self) -> Option<Range<usize>> {
+        Some(self)
+    }
+}
+
+impl IntoSpan for usize {
+    fn into_span(self) -> Option<Range<usize>> {
+        Some(self..self + 1)
+    }
+}
+
+/// Constructs a parse [`Error`]. See [`IntoSpan`] for what can be passed as
+/// `span`.
+pub(crate) fn perr(span: impl IntoSpan, kind: ErrorKind) -> Error {
+    Error { span: span.into_span(), kind }
+}
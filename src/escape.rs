@@ -0,0 +1,238 @@
+//! Shared logic for unescaping the escape sequences found in string, byte
+//! string and C-string literals.
+//!
+//! All three literal kinds agree on the bulk of the escape grammar (`\n`,
+//! `\r`, `\t`, `\\`, `\0`, `\'`, `\"`, `\xHH`, and for non-byte kinds
+//! `\u{...}`), but differ in a handful of rules: whether `\u{...}` is
+//! allowed at all, whether `\xHH` may produce a value above `0x7F`, whether
+//! non-ASCII source characters are allowed, and whether a NUL is forbidden.
+//! [`Mode`] captures exactly those differences (mirroring the `Mode` enum
+//! `rustc_lexer` uses for the same purpose, which also has `Char`/`Byte`
+//! variants for the char/byte literal kinds this crate doesn't support yet)
+//! so the escape grammar itself, [`unescape`] and [`unescape_with`], is
+//! implemented exactly once.
+
+use std::ops::Range;
+
+use crate::{ErrorKind, ErrorKind::*, err::{Error, perr}};
+
+
+/// Which of the three literal kinds an escape sequence is being processed
+/// for. Selects the handful of rules that differ between them; see the
+/// `allow_*`/`forbid_*` methods below for what each one means.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Mode {
+    Str,
+    ByteStr,
+    CStr,
+}
+
+impl Mode {
+    /// Whether `\u{...}` escapes are allowed.
+    fn allow_unicode_escape(self) -> bool {
+        !matches!(self, Self::ByteStr)
+    }
+
+    /// Whether a `\xHH` escape may produce a value above `0x7F`.
+    fn allow_high_x_escape(self) -> bool {
+        matches!(self, Self::ByteStr | Self::CStr)
+    }
+
+    /// Whether a non-ASCII character is allowed to appear verbatim (i.e.
+    /// not as part of an escape) in the source.
+    fn allow_non_ascii_source(self) -> bool {
+        !matches!(self, Self::ByteStr)
+    }
+
+    /// Whether a NUL byte/codepoint (written directly or produced by an
+    /// escape) is forbidden.
+    fn forbid_nul(self) -> bool {
+        matches!(self, Self::CStr)
+    }
+}
+
+/// One unit of a literal's value: either a full `char` or a single raw
+/// byte. `StringLit` only ever produces the `Char` variant. Under
+/// [`Mode::ByteStr`] and [`Mode::CStr`], a `\xHH` escape above `0x7F` has no
+/// meaning as a `char`, so it is kept as a raw byte instead of being UTF-8
+/// encoded; every other unit is a `Char`. `ByteStringLit` turns each unit
+/// into one byte via [`MixedUnit::as_byte`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum MixedUnit {
+    Char(char),
+    Byte(u8),
+}
+
+impl MixedUnit {
+    /// Extracts the `char` of a unit that is known to never be a raw byte
+    /// (i.e. produced under [`Mode::Str`]).
+    pub(crate) fn as_char(self) -> char {
+        match self {
+            Self::Char(c) => c,
+            Self::Byte(b) => unreachable!("byte {b:#x} unit outside of CStr mode"),
+        }
+    }
+
+    /// Extracts the byte value of a unit produced under [`Mode::ByteStr`],
+    /// where every `char` is guaranteed to be ASCII.
+    pub(crate) fn as_byte(self) -> u8 {
+        match self {
+            Self::Char(c) => c as u8,
+            Self::Byte(b) => b,
+        }
+    }
+
+    /// Whether this unit is a NUL, which is forbidden anywhere inside a
+    /// C-string.
+    pub(crate) fn is_nul(&self) -> bool {
+        matches!(self, Self::Char('\0') | Self::Byte(0))
+    }
+
+    /// Appends this unit's byte representation (a raw byte, or a `char`
+    /// encoded as UTF-8) to `buf`.
+    pub(crate) fn push_to(self, buf: &mut Vec<u8>) {
+        match self {
+            Self::Char(c) => buf.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes()),
+            Self::Byte(b) => buf.push(b),
+        }
+    }
+}
+
+/// Validates a character taken verbatim from the source (i.e. one that
+/// isn't part of an escape sequence) against `mode`'s rules.
+fn check_source_char(c: char, mode: Mode) -> Result<MixedUnit, ErrorKind> {
+    if !mode.allow_non_ascii_source() && !c.is_ascii() {
+        return Err(NonAsciiInByteLiteral);
+    }
+    if mode.forbid_nul() && c == '\0' {
+        return Err(NulInCStr);
+    }
+    Ok(MixedUnit::Char(c))
+}
+
+/// Unescapes a single escape sequence in `input` (which must start with the
+/// `\` that begins the sequence, i.e. `input.as_bytes()[0] == b'\\'`).
+/// `offset` is the absolute byte position of that `\` within whatever larger
+/// string the caller is reporting errors against, and is only used to
+/// translate the spans inside the returned [`Error`]; all indexing into
+/// `input` itself is relative.
+///
+/// Returns the resulting unit and the number of bytes of `input` (starting
+/// from its first byte) that the escape sequence occupies.
+pub(crate) fn unescape(input: &str, offset: usize, mode: Mode) -> Result<(MixedUnit, usize), Error> {
+    let bytes = input.as_bytes();
+    let first = bytes.get(1)
+        .ok_or(perr(offset..offset + input.len(), UnterminatedEscape))?;
+
+    let (unit, len) = match first {
+        b'n' => (MixedUnit::Char('\n'), 2),
+        b'r' => (MixedUnit::Char('\r'), 2),
+        b't' => (MixedUnit::Char('\t'), 2),
+        b'\\' => (MixedUnit::Char('\\'), 2),
+        b'0' => (MixedUnit::Char('\0'), 2),
+        b'\'' => (MixedUnit::Char('\''), 2),
+        b'"' => (MixedUnit::Char('"'), 2),
+
+        b'x' => {
+            let hex = bytes.get(2..4)
+                .ok_or(perr(offset..offset + input.len(), UnterminatedEscape))?;
+            let digits = std::str::from_utf8(hex).ok()
+                .filter(|s| s.chars().all(|c| c.is_ascii_hexdigit()))
+                .ok_or(perr(offset..offset + 4, InvalidXEscape))?;
+            let byte = u8::from_str_radix(digits, 16).unwrap();
+            if byte > 0x7F && !mode.allow_high_x_escape() {
+                return Err(perr(offset..offset + 4, NonAsciiXEscape));
+            }
+            let unit = if byte.is_ascii() { MixedUnit::Char(byte as char) } else { MixedUnit::Byte(byte) };
+            (unit, 4)
+        }
+
+        b'u' if mode.allow_unicode_escape() => {
+            if bytes.get(2) != Some(&b'{') {
+                return Err(perr(offset..offset + 2, UnicodeEscapeWithoutBrace));
+            }
+
+            let closing = input[3..].find('}')
+                .ok_or(perr(offset..offset + input.len(), UnterminatedUnicodeEscape))?;
+            let digits = &input[3..3 + closing];
+
+            let mut value: u32 = 0;
+            let mut num_digits = 0;
+            for (j, c) in digits.char_indices() {
+                let pos = offset + 3 + j;
+                match c {
+                    '_' if num_digits == 0 => return Err(perr(pos, InvalidStartOfUnicodeEscape)),
+                    '_' => {}
+                    c if c.is_ascii_hexdigit() => {
+                        if num_digits == 6 {
+                            return Err(perr(pos, TooManyDigitInUnicodeEscape));
+                        }
+                        value = value * 16 + c.to_digit(16).unwrap();
+                        num_digits += 1;
+                    }
+                    _ => return Err(perr(pos, NonHexDigitInUnicodeEscape)),
+                }
+            }
+
+            let end = 3 + closing;
+            let c = char::from_u32(value)
+                .ok_or(perr(offset..offset + end, InvalidUnicodeEscapeChar))?;
+            (MixedUnit::Char(c), end + 1)
+        }
+
+        _ => return Err(perr(offset..offset + 2, UnknownEscape)),
+    };
+
+    if mode.forbid_nul() && unit.is_nul() {
+        return Err(perr(offset..offset + len, NulInCStr));
+    }
+
+    Ok((unit, len))
+}
+
+/// Scans `input` (a literal's content, without surrounding quotes and
+/// without any `r#`/hash handling -- callers with a raw literal should not
+/// call this, since raw literals don't process escapes at all) unit by
+/// unit, invoking `cb` once per logical unit with the byte range within
+/// `input` (offset by `offset`, so callers can pass the position of `input`
+/// within some larger string) that produced it, together with either the
+/// decoded unit or the error that occurred while decoding it.
+///
+/// Unlike [`unescape`], this never stops at the first error: scanning
+/// continues afterwards so a caller can collect every problem in a literal
+/// in one pass, which is what editor/diagnostic tooling typically wants.
+pub(crate) fn unescape_with(
+    input: &str,
+    offset: usize,
+    mode: Mode,
+    mut cb: impl FnMut(Range<usize>, Result<MixedUnit, Error>),
+) {
+    let mut i = 0;
+    while i < input.len() {
+        if input.as_bytes()[i] == b'\\' {
+            match unescape(&input[i..], offset + i, mode) {
+                Ok((unit, len)) => {
+                    cb(offset + i..offset + i + len, Ok(unit));
+                    i += len;
+                }
+                Err(e) => {
+                    // Resume right after whatever span the error covers (or
+                    // just past the backslash if there is none), so that a
+                    // single bad escape doesn't get reported over and over.
+                    let resume_at = e.span().map_or(offset + i + 1, |s| s.end);
+                    cb(e.span().unwrap_or(offset + i..offset + i + 1), Err(e));
+                    i = (resume_at - offset).max(i + 1);
+                }
+            }
+        } else {
+            let c = input[i..].chars().next().expect("valid utf-8 with remaining bytes");
+            let len = c.len_utf8();
+            let span = offset + i..offset + i + len;
+            match check_source_char(c, mode) {
+                Ok(unit) => cb(span, Ok(unit)),
+                Err(kind) => cb(span.clone(), Err(perr(span, kind))),
+            }
+            i += len;
+        }
+    }
+}
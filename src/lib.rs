@@ -0,0 +1,142 @@
+//! Parsing and inspecting Rust literals, i.e. tokens in the Rust programming
+//! language that represent fixed values, like `27`, `"hello"` or `true`.
+//!
+//! This crate is mainly useful for proc macros, which receive literals as
+//! opaque [`proc_macro::Literal`][pm-lit] tokens (or as strings). This crate
+//! lets you parse and inspect such a literal without depending on the
+//! `proc-macro` crate (e.g. for use in a build script, or in a library that
+//! is also used outside of a proc macro).
+//!
+//! [pm-lit]: https://doc.rust-lang.org/proc_macro/struct.Literal.html
+
+use std::{borrow::Cow, fmt, ops::{Deref, Range}};
+
+mod bool;
+mod bytestr;
+mod cstring;
+mod err;
+mod escape;
+mod raw_string;
+mod string;
+
+#[cfg(test)]
+mod test_util;
+
+pub use self::{
+    bool::BoolLit,
+    bytestr::ByteStringLit,
+    cstring::CStringLit,
+    err::{Error, ErrorKind},
+    string::StringLit,
+};
+
+
+/// One of the literals defined in the Rust reference, e.g. `27`, `"hello"`
+/// or `true`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Literal<B: Buffer> {
+    Bool(BoolLit),
+    String(StringLit<B>),
+    ByteString(ByteStringLit<B>),
+    CString(CStringLit<B>),
+}
+
+impl<B: Buffer> Literal<B> {
+    /// Parses the given input as any kind of literal.
+    pub fn parse(input: B) -> Result<Self, Error> {
+        if input.is_empty() {
+            return Err(err::perr(None, ErrorKind::Empty));
+        }
+
+        if input.starts_with("c\"") || input.starts_with("cr") {
+            return CStringLit::parse(input).map(Self::CString);
+        }
+        if input.starts_with("b\"") || input.starts_with("br") {
+            return ByteStringLit::parse(input).map(Self::ByteString);
+        }
+        if input.starts_with('"') || input.starts_with('r') {
+            return StringLit::parse(input).map(Self::String);
+        }
+        if let Ok(b) = BoolLit::parse(&input) {
+            return Ok(Self::Bool(b));
+        }
+
+        Err(err::perr(None, ErrorKind::InvalidLiteral))
+    }
+}
+
+impl<B: Buffer> fmt::Display for Literal<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Bool(l) => l.fmt(f),
+            Self::String(l) => l.fmt(f),
+            Self::ByteString(l) => l.fmt(f),
+            Self::CString(l) => l.fmt(f),
+        }
+    }
+}
+
+
+/// The input buffer type a literal was parsed from: either a borrowed `&str`
+/// or an owned `String`.
+///
+/// This trait is sealed and cannot be implemented outside of this crate.
+pub trait Buffer:
+    Deref<Target = str> + sealed::Sealed + Clone + fmt::Debug
+{
+    /// An owned-or-borrowed `[u8]`, returned by the `into_value` methods of
+    /// byte-valued literals (`ByteStringLit`, `CStringLit`).
+    type ByteCow: From<Vec<u8>> + fmt::Debug + Clone + PartialEq;
+
+    /// An owned-or-borrowed `str`, returned by `into_value` of `StringLit`.
+    type Cow: From<String> + fmt::Debug + Clone + PartialEq;
+
+    /// Cuts down the buffer to the given byte range.
+    #[doc(hidden)]
+    fn cut(self, range: Range<usize>) -> Self;
+
+    #[doc(hidden)]
+    fn into_byte_cow(self) -> Self::ByteCow;
+
+    #[doc(hidden)]
+    fn into_cow(self) -> Self::Cow;
+}
+
+impl<'a> Buffer for &'a str {
+    type ByteCow = Cow<'a, [u8]>;
+    type Cow = Cow<'a, str>;
+
+    fn cut(self, range: Range<usize>) -> Self {
+        &self[range]
+    }
+    fn into_byte_cow(self) -> Self::ByteCow {
+        Cow::Borrowed(self.as_bytes())
+    }
+    fn into_cow(self) -> Self::Cow {
+        Cow::Borrowed(self)
+    }
+}
+
+impl Buffer for String {
+    type ByteCow = Cow<'static, [u8]>;
+    type Cow = Cow<'static, str>;
+
+    fn cut(mut self, range: Range<usize>) -> Self {
+        self.truncate(range.end);
+        self.drain(..range.start);
+        self
+    }
+    fn into_byte_cow(self) -> Self::ByteCow {
+        Cow::Owned(self.into_bytes())
+    }
+    fn into_cow(self) -> Self::Cow {
+        Cow::Owned(self)
+    }
+}
+
+mod sealed {
+    pub trait Sealed {}
+    impl Sealed for &str {}
+    impl Sealed for String {}
+}
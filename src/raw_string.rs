@@ -0,0 +1,52 @@
+//! Shared logic for scanning the body of a raw literal (`r"..."`,
+//! `br"..."` or `cr"..."`): finding the matching closing quote (accounting
+//! for the number of `#`s) and rejecting trailing content after it. Raw
+//! literals never contain escapes, so this is the entire parse for their
+//! non-prefix part; the only thing that differs between the three kinds is
+//! which bytes are allowed to appear verbatim in between, which callers
+//! supply as `validate_byte`.
+
+use crate::{ErrorKind::*, Error, err::perr};
+
+
+/// Scans `input` (the full literal, including its `r`/`br`/`cr` prefix) for
+/// the closing quote and returns the number of `#`s found, or an error if
+/// the input isn't validly delimited.
+///
+/// `prefix_len` is the number of bytes before the first `#`/`"` (1 for `r`,
+/// 2 for `br`/`cr`). `validate_byte` is called once for every byte strictly
+/// between the opening quote and the closing `"###...` sequence, with its
+/// absolute position in `input`, to apply whatever rule (allowed character
+/// set, forbidden NUL, isolated `\r`) distinguishes the three literal
+/// kinds; returning `Err` aborts the scan with that error.
+pub(crate) fn scan_raw_body(
+    input: &str,
+    prefix_len: usize,
+    mut validate_byte: impl FnMut(usize, u8) -> Result<(), Error>,
+) -> Result<u32, Error> {
+    let num_hashes = input[prefix_len..].bytes().position(|b| b != b'#')
+        .ok_or(perr(None, InvalidLiteral))?;
+
+    if input.as_bytes().get(prefix_len + num_hashes) != Some(&b'"') {
+        return Err(perr(None, InvalidLiteral));
+    }
+    let start_inner = prefix_len + num_hashes + 1;
+    let hashes = &input[prefix_len..prefix_len + num_hashes];
+
+    let mut closing_quote_pos = None;
+    for (i, &b) in input.as_bytes()[start_inner..].iter().enumerate() {
+        let i = i + start_inner;
+        if b == b'"' && input[i + 1..].starts_with(hashes) {
+            closing_quote_pos = Some(i);
+            break;
+        }
+        validate_byte(i, b)?;
+    }
+    let closing_quote_pos = closing_quote_pos.ok_or(perr(None, UnterminatedRawString))?;
+
+    if closing_quote_pos + num_hashes != input.len() - 1 {
+        return Err(perr(closing_quote_pos + num_hashes + 1..input.len(), UnexpectedChar));
+    }
+
+    Ok(num_hashes as u32)
+}
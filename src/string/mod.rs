@@ -0,0 +1,274 @@
+use std::{fmt, ops::Range};
+
+use crate::{Buffer, Error, ErrorKind::*, err::perr, escape::{self, Mode, unescape}, raw_string};
+
+
+/// A string or raw string literal, e.g. `"hello"` or `r#"abc"def"#`.
+///
+/// See [the reference][ref] for more information.
+///
+/// [ref]: https://doc.rust-lang.org/reference/tokens.html#string-literals
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StringLit<B: Buffer> {
+    /// The raw input.
+    raw: B,
+
+    /// The string value (with all escapes unescaped), or `None` if there
+    /// were no escapes. In the latter case, `inner_range` of `raw` is the
+    /// string value.
+    value: Option<String>,
+
+    /// The number of hash signs in case of a raw string literal, or `None`
+    /// if it's not a raw string literal.
+    num_hashes: Option<u32>,
+}
+
+impl<B: Buffer> StringLit<B> {
+    /// Parses the input as a (raw) string literal. Returns an error if the
+    /// input is invalid or represents a different kind of literal.
+    pub fn parse(input: B) -> Result<Self, Error> {
+        if input.is_empty() {
+            return Err(perr(None, Empty));
+        }
+        if !input.starts_with('"') && !input.starts_with('r') {
+            return Err(perr(None, InvalidLiteral));
+        }
+
+        Self::parse_impl(input)
+    }
+
+    /// Like [`Self::parse`], but keeps scanning after a malformed escape
+    /// instead of stopping at the first one, so a caller can collect every
+    /// escape problem in the literal in one pass (useful for e.g. an IDE
+    /// that wants to underline every bad escape at once instead of making
+    /// the user fix one and re-run).
+    ///
+    /// Returns every escape error encountered, in source order, and `Some`
+    /// literal only if there were none: a `StringLit` that callers can
+    /// inspect further (e.g. via [`Self::value`] or
+    /// [`Self::raw_range_of_value_index`]) must have a fully decoded value,
+    /// so a literal with one or more bad escapes yields `None` alongside
+    /// its errors. An error that concerns the literal as a whole (e.g. a
+    /// missing closing quote) is returned alone. A raw string literal never
+    /// has escapes, so its `Vec` holds at most one error.
+    pub fn parse_collecting(input: B) -> (Option<Self>, Vec<Error>) {
+        if input.is_empty() {
+            return (None, vec![perr(None, Empty)]);
+        }
+        if !input.starts_with('"') && !input.starts_with('r') {
+            return (None, vec![perr(None, InvalidLiteral)]);
+        }
+
+        Self::parse_collecting_impl(input)
+    }
+
+    /// Returns the string value this literal represents (where all escapes
+    /// have been turned into their respective values).
+    pub fn value(&self) -> &str {
+        self.value.as_deref().unwrap_or(&self.raw[self.inner_range()])
+    }
+
+    /// Like `value` but returns a potentially owned version of the value.
+    ///
+    /// The return value is either `Cow<'static, str>` if `B = String`, or
+    /// `Cow<'a, str>` if `B = &'a str`.
+    pub fn into_value(self) -> B::Cow {
+        let inner_range = self.inner_range();
+        let Self { raw, value, .. } = self;
+        value.map(B::Cow::from).unwrap_or_else(|| raw.cut(inner_range).into_cow())
+    }
+
+    /// Returns whether this literal is a raw string literal (starting with
+    /// `r`).
+    pub fn is_raw_string(&self) -> bool {
+        self.num_hashes.is_some()
+    }
+
+    /// Returns the byte range within `self.raw` (i.e. the original,
+    /// possibly-escaped source) that produced the byte at `value_idx` within
+    /// `self.value()`.
+    ///
+    /// This is the inverse of the mapping escapes create: a single escape
+    /// like `\u{1f602}` occupies many bytes of `raw` but produces only a few
+    /// bytes of `value`, and a plain (unescaped) character occupies the same
+    /// number of bytes in both. Every byte of such a unit maps back to the
+    /// full raw span that produced it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `value_idx` is not a valid byte index into `self.value()`.
+    pub fn raw_range_of_value_index(&self, value_idx: usize) -> Range<usize> {
+        assert!(value_idx < self.value().len(), "value index out of bounds");
+
+        let inner = self.inner_range();
+        if self.value.is_none() {
+            // No escapes: `value` is a verbatim copy of `raw[inner]`, so the
+            // unit is the char of `value` that contains `value_idx`.
+            let value = self.value();
+            let start = (0..=value_idx).rev().find(|&j| value.is_char_boundary(j)).unwrap();
+            let c_len = value[start..].chars().next().expect("valid utf-8").len_utf8();
+            return inner.start + start..inner.start + start + c_len;
+        }
+
+        let mut value_pos = 0;
+        let mut i = inner.start;
+        while i < inner.end {
+            if self.raw.as_bytes()[i] == b'\\' {
+                let (unit, len) = unescape(&self.raw[i..inner.end], i, Mode::Str)
+                    .expect("already validated while parsing");
+                let c_len = unit.as_char().len_utf8();
+                if value_idx < value_pos + c_len {
+                    return i..i + len;
+                }
+                value_pos += c_len;
+                i += len;
+            } else {
+                let c = self.raw[i..].chars().next().expect("valid utf-8");
+                let c_len = c.len_utf8();
+                if value_idx < value_pos + c_len {
+                    return i..i + c_len;
+                }
+                value_pos += c_len;
+                i += c_len;
+            }
+        }
+        unreachable!("value_idx was already checked to be in bounds")
+    }
+
+    /// Iterates over the unescaped value of this literal without allocating,
+    /// invoking `cb` once per `char` with the range in `self.raw` (i.e. the
+    /// original, possibly escaped, source) that produced it, together with
+    /// either the `char` or the error that occurred while decoding it.
+    ///
+    /// Scanning continues after an error instead of stopping at the first
+    /// one, so a single call can be used to collect every problem in the
+    /// literal at once.
+    pub fn unescape_with(&self, mut cb: impl FnMut(Range<usize>, Result<char, Error>)) {
+        let inner = self.inner_range();
+        if self.is_raw_string() {
+            for (i, c) in self.raw[inner.clone()].char_indices() {
+                let start = inner.start + i;
+                cb(start..start + c.len_utf8(), Ok(c));
+            }
+        } else {
+            escape::unescape_with(
+                &self.raw[inner.clone()], inner.start, Mode::Str,
+                |range, res| cb(range, res.map(|u| u.as_char())),
+            );
+        }
+    }
+
+    /// The range within `self.raw` that excludes the quotes and potential
+    /// `r#`.
+    fn inner_range(&self) -> Range<usize> {
+        match self.num_hashes {
+            None => 1..self.raw.len() - 1,
+            Some(n) => 2 + n as usize..self.raw.len() - n as usize - 1,
+        }
+    }
+
+    /// Precondition: input has to start with either `"` or `r`.
+    pub(crate) fn parse_impl(input: B) -> Result<Self, Error> {
+        match Self::parse_collecting_impl(input) {
+            (Some(lit), errors) if errors.is_empty() => Ok(lit),
+            (_, mut errors) => Err(errors.remove(0)),
+        }
+    }
+
+    /// Precondition: input has to start with either `"` or `r`. See
+    /// [`Self::parse_collecting`] for the semantics.
+    pub(crate) fn parse_collecting_impl(input: B) -> (Option<Self>, Vec<Error>) {
+        if input.starts_with('r') {
+            return match Self::parse_raw_impl(input) {
+                Ok(lit) => (Some(lit), Vec::new()),
+                Err(e) => (None, vec![e]),
+            };
+        }
+
+        // First, find the closing quote without decoding any escapes: every
+        // escape's textual form (digits, `x`/`u`, braces) consists of bytes
+        // that never collide with a literal `"` or `\r`, so skipping the `\`
+        // together with whatever byte follows it is enough to avoid
+        // mistaking an escaped `\"` or `\\` for the end of the literal.
+        let mut i = 1;
+        while i < input.len() - 1 {
+            match input.as_bytes()[i] {
+                b'\\' => i += 2.min(input.len() - i),
+                b'\r' if input.as_bytes()[i + 1] != b'\n'
+                    => return (None, vec![perr(i, IsolatedCr)]),
+                b'"' => return (None, vec![perr(i + 1..input.len(), UnexpectedChar)]),
+                _ => i += input[i..].chars().next().map_or(1, char::len_utf8),
+            }
+        }
+
+        // `i` only overshoots to `input.len()` if the last `"` was escaped.
+        if i == input.len() || input.as_bytes()[input.len() - 1] != b'"' || input.len() == 1 {
+            return (None, vec![perr(None, UnterminatedString)]);
+        }
+
+        // Now that the literal's extent is known, decode its content with
+        // the shared escape engine, which already knows how to collect
+        // every error instead of stopping at the first one.
+        let mut had_escape = false;
+        let mut value = String::new();
+        let mut errors = Vec::new();
+        escape::unescape_with(&input[1..input.len() - 1], 1, Mode::Str, |range, res| {
+            match res {
+                Ok(unit) => {
+                    had_escape |= input.as_bytes()[range.start] == b'\\';
+                    value.push(unit.as_char());
+                }
+                Err(e) => errors.push(e),
+            }
+        });
+
+        if !errors.is_empty() {
+            return (None, errors);
+        }
+
+        // `value` is only `None` if there was no escape in the input string
+        // (with the special case of the input being empty). This means the
+        // string value basically equals the input, so we store `None`.
+        let value = if had_escape { Some(value) } else { None };
+        (Some(Self { raw: input, value, num_hashes: None }), Vec::new())
+    }
+
+    /// Precondition: input has to start with `r`. Raw string literals never
+    /// contain escapes, so there's never more than one error to report.
+    fn parse_raw_impl(input: B) -> Result<Self, Error> {
+        let num_hashes = raw_string::scan_raw_body(&input, 1, |i, b| {
+            if b == b'\r' && input.as_bytes().get(i + 1) != Some(&b'\n') {
+                return Err(perr(i, IsolatedCr));
+            }
+            Ok(())
+        })?;
+
+        Ok(Self {
+            raw: input,
+            value: None,
+            num_hashes: Some(num_hashes),
+        })
+    }
+}
+
+impl StringLit<&str> {
+    /// Makes a copy of the underlying buffer and returns the owned version
+    /// of `Self`.
+    pub fn into_owned(self) -> StringLit<String> {
+        StringLit {
+            raw: self.raw.to_owned(),
+            value: self.value,
+            num_hashes: self.num_hashes,
+        }
+    }
+}
+
+impl<B: Buffer> fmt::Display for StringLit<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(&self.raw)
+    }
+}
+
+
+#[cfg(test)]
+mod tests;
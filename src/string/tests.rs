@@ -1,4 +1,4 @@
-use crate::{Literal, StringLit, test_util::assert_parse_ok_eq};
+use crate::{assert_err, ErrorKind, Literal, StringLit, test_util::assert_parse_ok_eq};
 
 // ===== Utility functions =======================================================================
 
@@ -41,7 +41,7 @@ fn special_whitespace() {
     for &s in &strings {
         let input = format!(r#""{}""#, s);
         let input_raw = format!(r#"r"{}""#, s);
-        for (input, num_hashes) in vec![(input, None), (input_raw, Some(0))] {
+        for (input, num_hashes) in [(input, None), (input_raw, Some(0))] {
             let expected = StringLit {
                 raw: &*input,
                 value: None,
@@ -210,3 +210,86 @@ fn invalid_unicode_escapes() {
 
     assert_err!(StringLit, r#""\u{110000}fox""#, InvalidUnicodeEscapeChar, 1..10);
 }
+
+#[test]
+fn parse_collecting_multiple_errors() {
+    let (lit, errors) = StringLit::parse_collecting(r#""\a\y""#);
+    assert_eq!(lit, None);
+    let kinds_and_spans: Vec<_> = errors.iter().map(|e| (e.kind(), e.span())).collect();
+    assert_eq!(kinds_and_spans, vec![
+        (ErrorKind::UnknownEscape, Some(1..3)),
+        (ErrorKind::UnknownEscape, Some(3..5)),
+    ]);
+}
+
+#[test]
+fn parse_collecting_unterminated_string_is_single_error() {
+    let (lit, errors) = StringLit::parse_collecting(r#""foo"#);
+    assert_eq!(lit, None);
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].kind(), ErrorKind::UnterminatedString);
+    assert_eq!(errors[0].span(), None);
+}
+
+#[test]
+fn parse_collecting_ok_has_no_errors() {
+    let (lit, errors) = StringLit::parse_collecting(r#""a\nb""#);
+    assert!(errors.is_empty());
+    assert_eq!(lit.unwrap().value(), "a\nb");
+}
+
+#[test]
+fn parse_agrees_with_parse_collecting_first_error() {
+    let (_, mut errors) = StringLit::parse_collecting(r#""\a\y""#);
+    let first = errors.remove(0);
+    let err = StringLit::parse(r#""\a\y""#).unwrap_err();
+    assert_eq!(err, first);
+}
+
+#[test]
+fn unescape_with_values_and_ranges() {
+    let lit = StringLit::parse("\"a\\nb\\u{1f602}c\"").unwrap();
+    let mut out = Vec::new();
+    lit.unescape_with(|range, res| out.push((range, res.unwrap())));
+    assert_eq!(out, vec![
+        (1..2, 'a'),
+        (2..4, '\n'),
+        (4..5, 'b'),
+        (5..14, '\u{1f602}'),
+        (14..15, 'c'),
+    ]);
+}
+
+#[test]
+fn raw_range_of_value_index_no_escapes() {
+    let lit = StringLit::parse("\"héllo\"").unwrap();
+    assert_eq!(lit.value(), "héllo");
+    // Both bytes of 'é' map to its full two-byte span.
+    let expected = [1..2, 2..4, 2..4, 4..5, 5..6, 6..7];
+    for i in 0..lit.value().len() {
+        assert_eq!(lit.raw_range_of_value_index(i), expected[i].clone());
+    }
+}
+
+#[test]
+fn raw_range_of_value_index_with_escape_and_verbatim_multi_byte_char() {
+    let lit = StringLit::parse("\"a\\nü\"").unwrap();
+    assert_eq!(lit.value(), "a\nü");
+    assert_eq!(lit.raw_range_of_value_index(0), 1..2); // 'a'
+    assert_eq!(lit.raw_range_of_value_index(1), 2..4); // '\n'
+    assert_eq!(lit.raw_range_of_value_index(2), 4..6); // 'ü', first byte
+    assert_eq!(lit.raw_range_of_value_index(3), 4..6); // 'ü', second byte
+}
+
+#[test]
+fn unescape_with_raw_string() {
+    let lit = StringLit::parse("r\"a\\nb\"").unwrap();
+    let mut out = Vec::new();
+    lit.unescape_with(|range, res| out.push((range, res.unwrap())));
+    assert_eq!(out, vec![
+        (2..3, 'a'),
+        (3..4, '\\'),
+        (4..5, 'n'),
+        (5..6, 'b'),
+    ]);
+}
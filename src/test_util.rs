@@ -0,0 +1,41 @@
+//! Small helpers shared by the `tests` modules of the individual literal
+//! kinds. Not part of the public API.
+
+use std::fmt::Debug;
+
+use crate::Error;
+
+
+/// Asserts that `actual` is `Ok(expected)`, panicking with a message that
+/// includes `input` and `what` (e.g. `"StringLit::parse"`) otherwise.
+pub(crate) fn assert_parse_ok_eq<T: Debug + PartialEq>(
+    input: &str,
+    actual: Result<T, Error>,
+    expected: T,
+    what: &str,
+) {
+    match actual {
+        Ok(actual) => assert_eq!(actual, expected, "{} returned unexpected value for {:?}", what, input),
+        Err(e) => panic!("{} unexpectedly failed for {:?}: {:?}", what, input, e),
+    }
+}
+
+/// Asserts that `$ty::parse($input)` fails with the given [`crate::ErrorKind`]
+/// and span. `$span` may be `None`, a `usize` (a single byte) or a
+/// `Range<usize>`, just like the arguments accepted by `err::perr`.
+#[macro_export]
+macro_rules! assert_err {
+    ($ty:ident, $input:expr, $kind:ident, $span:expr) => {
+        match $ty::parse($input) {
+            Ok(lit) => panic!("expected a parse error for {:?}, but got {:?}", $input, lit),
+            Err(e) => {
+                assert_eq!(e.kind(), $crate::ErrorKind::$kind, "wrong error kind for {:?}", $input);
+                assert_eq!(
+                    e.span(),
+                    $crate::err::IntoSpan::into_span($span),
+                    "wrong error span for {:?}", $input,
+                );
+            }
+        }
+    };
+}